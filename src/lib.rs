@@ -1,81 +1,251 @@
 use std::{
     borrow::Borrow,
-    collections::hash_map::DefaultHasher,
-    hash::{Hash, Hasher},
+    collections::hash_map::RandomState,
+    error::Error,
+    fmt,
+    hash::{BuildHasher, Hash},
     mem,
 };
 
-const INITIAL_BUCKETS: usize = 1;
+mod set;
+#[cfg(feature = "serde")]
+mod serde_impl;
 
-pub struct HashMap<K, V> {
-    buckets: Vec<Vec<(K, V)>>,
+pub use set::HashSet;
+
+const INITIAL_CAPACITY: usize = 4;
+
+enum Slot<K, V> {
+    Empty,
+    Tombstone,
+    Full(u64, K, V),
+}
+
+enum Probe {
+    Occupied(usize),
+    Vacant(usize),
+}
+
+pub struct HashMap<K, V, S = RandomState> {
+    slots: Vec<Slot<K, V>>,
     len: usize,
+    tombstones: usize,
+    hash_builder: S,
 }
 
-impl<K, V> HashMap<K, V>
-where
-    K: Hash + Eq,
-{
+impl<K, V> HashMap<K, V, RandomState> {
     pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V> Default for HashMap<K, V, RandomState> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, S> HashMap<K, V, S> {
+    pub fn with_hasher(hash_builder: S) -> Self {
         Self {
-            buckets: Vec::new(),
+            slots: Vec::new(),
             len: 0,
+            tombstones: 0,
+            hash_builder,
+        }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        let mut map = Self::with_hasher(hash_builder);
+        if capacity > 0 {
+            let mut target_capacity = INITIAL_CAPACITY;
+            while target_capacity * 7 < capacity * 8 {
+                target_capacity *= 2;
+            }
+            map.slots = (0..target_capacity).map(|_| Slot::Empty).collect();
         }
+        map
     }
 
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<K, V, S> HashMap<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
     pub fn insert(&mut self, key: K, value: V) -> Option<V> {
-        if self.buckets.is_empty() || self.len > (3 * self.buckets.len() / 4) {
+        self.reserve_for_one_more();
+        self.insert_after_reserve(key, value)
+    }
+
+    /// Like [`insert`](Self::insert), but reports allocation failure instead of panicking.
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, TryReserveError> {
+        if self.needs_growth_for_one_more() {
+            self.try_reserve(1)?;
+        }
+        Ok(self.insert_after_reserve(key, value))
+    }
+
+    fn insert_after_reserve(&mut self, key: K, value: V) -> Option<V> {
+        let hash = self.hash_of(&key);
+        match self.probe(hash, &key) {
+            Probe::Occupied(index) => match &mut self.slots[index] {
+                Slot::Full(_, _, v) => Some(mem::replace(v, value)),
+                _ => unreachable!("probe reported an occupied slot that isn't Full"),
+            },
+            Probe::Vacant(index) => {
+                if matches!(self.slots[index], Slot::Tombstone) {
+                    self.tombstones -= 1;
+                }
+                self.slots[index] = Slot::Full(hash, key, value);
+                self.len += 1;
+                None
+            }
+        }
+    }
+
+    fn needs_growth_for_one_more(&self) -> bool {
+        self.slots.is_empty() || (self.len + self.tombstones + 1) * 8 > self.slots.len() * 7
+    }
+
+    fn reserve_for_one_more(&mut self) {
+        if self.needs_growth_for_one_more() {
             self.resize_buckets();
         }
+    }
 
-        let bucket_index = self
-            .get_bucket(&key)
-            .expect("Bucket is empty. That can't be");
-        let bucket = &mut self.buckets[bucket_index];
+    /// Reserves capacity for at least `additional` more entries, growing
+    /// straight to the target bucket count rather than doubling repeatedly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes or the
+    /// allocator reports an allocation failure. Use [`try_reserve`](Self::try_reserve)
+    /// to handle the fallible case instead.
+    pub fn reserve(&mut self, additional: usize) {
+        self.try_reserve(additional)
+            .unwrap_or_else(|e| panic!("{e}"));
+    }
 
-        for (k, v) in bucket.iter_mut() {
-            if &key == k {
-                return Some(std::mem::replace(v, value));
-            }
+    /// Like [`reserve`](Self::reserve), but returns a [`TryReserveError`] instead
+    /// of panicking if the allocator reports an allocation failure. The map is
+    /// left untouched on error.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let required = self
+            .len
+            .checked_add(self.tombstones)
+            .and_then(|n| n.checked_add(additional))
+            .expect("capacity overflow");
+        let required_slots = required.saturating_mul(8);
+        let mut target_capacity = INITIAL_CAPACITY.max(self.slots.len());
+        while target_capacity.saturating_mul(7) < required_slots {
+            target_capacity = target_capacity.saturating_mul(2);
         }
 
-        bucket.push((key, value));
-        self.len += 1;
-        None
+        if target_capacity <= self.slots.len() {
+            return Ok(());
+        }
+
+        let mut new_slots = Vec::new();
+        new_slots
+            .try_reserve_exact(target_capacity)
+            .map_err(TryReserveError)?;
+        new_slots.extend((0..target_capacity).map(|_| Slot::Empty));
+
+        self.rehash_into(new_slots);
+        Ok(())
     }
 
-    fn get_bucket<Q>(&self, key: &Q) -> Option<usize>
+    fn hash_of<Q>(&self, key: &Q) -> u64
+    where
+        K: Borrow<Q>,
+        Q: Hash + ?Sized,
+    {
+        self.hash_builder.hash_one(key)
+    }
+
+    /// Walks the triangular probe sequence for `key` starting at its home slot,
+    /// stopping at the first `Empty` slot. Returns the matching slot on a hit,
+    /// or the first `Tombstone` (falling back to the terminating `Empty`) seen
+    /// along the way so inserts can reuse it.
+    fn probe<Q>(&self, hash: u64, key: &Q) -> Probe
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let mask = self.slots.len() - 1;
+        let mut index = (hash & mask as u64) as usize;
+        let mut first_tombstone = None;
+        let mut step = 1;
+
+        loop {
+            match &self.slots[index] {
+                Slot::Full(slot_hash, k, _) if *slot_hash == hash && k.borrow() == key => {
+                    return Probe::Occupied(index);
+                }
+                Slot::Full(..) => {}
+                Slot::Tombstone if first_tombstone.is_none() => {
+                    first_tombstone = Some(index);
+                }
+                Slot::Tombstone => {}
+                Slot::Empty => return Probe::Vacant(first_tombstone.unwrap_or(index)),
+            }
+
+            index = (index + step) & mask;
+            step += 1;
+        }
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<usize>
     where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        if self.buckets.is_empty() {
-            None
-        } else {
-            let mut hasher = DefaultHasher::new();
-            key.hash(&mut hasher);
-            Some((hasher.finish() % self.buckets.len() as u64) as usize)
+        if self.slots.is_empty() {
+            return None;
+        }
+
+        match self.probe(self.hash_of(key), key) {
+            Probe::Occupied(index) => Some(index),
+            Probe::Vacant(_) => None,
         }
     }
 
     fn resize_buckets(&mut self) {
-        let target_size = match self.buckets.len() {
-            0 => INITIAL_BUCKETS,
+        let target_capacity = match self.slots.len() {
+            0 => INITIAL_CAPACITY,
             n => n * 2,
         };
 
-        let mut new_buckets = Vec::with_capacity(target_size);
-        new_buckets.extend((0..target_size).map(|_| Vec::new()));
-
-        for (key, value) in self.buckets.iter_mut().flat_map(|bucket| bucket.drain(..)) {
-            let mut hasher = DefaultHasher::new();
-            key.hash(&mut hasher);
+        self.rehash_into((0..target_capacity).map(|_| Slot::Empty).collect());
+    }
 
-            let nbucket_index = (hasher.finish() % new_buckets.len() as u64) as usize;
-            new_buckets[nbucket_index].push((key, value));
+    /// Moves every live entry from `self.slots` into `new_slots`, which must
+    /// already be sized to the target power-of-two capacity.
+    fn rehash_into(&mut self, new_slots: Vec<Slot<K, V>>) {
+        let old_slots = mem::replace(&mut self.slots, new_slots);
+        self.tombstones = 0;
+
+        let mask = self.slots.len() - 1;
+        for slot in old_slots {
+            if let Slot::Full(hash, key, value) = slot {
+                let mut index = (hash & mask as u64) as usize;
+                let mut step = 1;
+                while !matches!(self.slots[index], Slot::Empty) {
+                    index = (index + step) & mask;
+                    step += 1;
+                }
+                self.slots[index] = Slot::Full(hash, key, value);
+            }
         }
-
-        mem::replace(&mut self.buckets, new_buckets);
     }
 
     pub fn contains_key<Q>(&self, key: &Q) -> bool
@@ -91,11 +261,14 @@ where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        let bucket_index = self.get_bucket(key)?;
-        let bucket = &mut self.buckets[bucket_index];
-        let index = bucket.iter().position(|(k, v)| k.borrow() == key)?;
+        let index = self.find(key)?;
+        let slot = mem::replace(&mut self.slots[index], Slot::Tombstone);
         self.len -= 1;
-        Some(bucket.swap_remove(index))
+        self.tombstones += 1;
+        match slot {
+            Slot::Full(_, k, v) => Some((k, v)),
+            _ => unreachable!("find returned a slot that isn't Full"),
+        }
     }
 
     pub fn get<Q>(&self, key: &Q) -> Option<&V>
@@ -103,68 +276,427 @@ where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        let bucket_index = self.get_bucket(key)?;
-        self.buckets[bucket_index]
-            .iter()
-            .find(|(ref k, _)| k.borrow() == key)
-            .map(|(ref k, ref v)| v)
+        let index = self.find(key)?;
+        match &self.slots[index] {
+            Slot::Full(_, _, v) => Some(v),
+            _ => unreachable!("find returned a slot that isn't Full"),
+        }
     }
 
-    pub fn len(&self) -> usize {
-        self.len
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        match &mut self.slots[index] {
+            Slot::Full(_, _, v) => Some(v),
+            _ => unreachable!("find returned a slot that isn't Full"),
+        }
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.len == 0
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V, S> {
+        let remaining = self.len;
+        IterMut {
+            map: self,
+            at: 0,
+            remaining,
+        }
+    }
+
+    /// Removes every key-value pair, returning them as an iterator and
+    /// leaving the map empty. Entries that are never pulled out of the
+    /// returned iterator are dropped along with it.
+    pub fn drain(&mut self) -> Drain<'_, K, V, S> {
+        let remaining = self.len;
+        self.tombstones = 0;
+        Drain {
+            map: self,
+            at: 0,
+            remaining,
+        }
+    }
+
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        self.reserve_for_one_more();
+
+        let hash = self.hash_of(&key);
+        match self.probe(hash, &key) {
+            Probe::Occupied(index) => Entry::Occupied(OccupiedEntry {
+                slots: &mut self.slots,
+                index,
+                len: &mut self.len,
+                tombstones: &mut self.tombstones,
+            }),
+            Probe::Vacant(index) => Entry::Vacant(VacantEntry {
+                slots: &mut self.slots,
+                index,
+                hash,
+                key,
+                len: &mut self.len,
+                tombstones: &mut self.tombstones,
+            }),
+        }
+    }
+}
+
+pub enum Entry<'a, K, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(default),
+        }
+    }
+
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(default()),
+        }
+    }
+
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        match self {
+            Entry::Occupied(mut e) => {
+                f(e.get_mut());
+                Entry::Occupied(e)
+            }
+            Entry::Vacant(e) => Entry::Vacant(e),
+        }
+    }
+}
+
+pub struct OccupiedEntry<'a, K, V> {
+    slots: &'a mut Vec<Slot<K, V>>,
+    index: usize,
+    len: &'a mut usize,
+    tombstones: &'a mut usize,
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    pub fn get(&self) -> &V {
+        match &self.slots[self.index] {
+            Slot::Full(_, _, v) => v,
+            _ => unreachable!("occupied entry points at a slot that isn't Full"),
+        }
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        match &mut self.slots[self.index] {
+            Slot::Full(_, _, v) => v,
+            _ => unreachable!("occupied entry points at a slot that isn't Full"),
+        }
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        match &mut self.slots[self.index] {
+            Slot::Full(_, _, v) => v,
+            _ => unreachable!("occupied entry points at a slot that isn't Full"),
+        }
+    }
+
+    pub fn insert(&mut self, value: V) -> V {
+        match &mut self.slots[self.index] {
+            Slot::Full(_, _, v) => mem::replace(v, value),
+            _ => unreachable!("occupied entry points at a slot that isn't Full"),
+        }
+    }
+
+    pub fn remove(self) -> V {
+        let slot = mem::replace(&mut self.slots[self.index], Slot::Tombstone);
+        *self.len -= 1;
+        *self.tombstones += 1;
+        match slot {
+            Slot::Full(_, _, v) => v,
+            _ => unreachable!("occupied entry points at a slot that isn't Full"),
+        }
     }
 }
 
-pub struct Iter<'a, K, V> {
-    map: &'a HashMap<K, V>,
-    bucket: usize,
+pub struct VacantEntry<'a, K, V> {
+    slots: &'a mut Vec<Slot<K, V>>,
+    index: usize,
+    hash: u64,
+    key: K,
+    len: &'a mut usize,
+    tombstones: &'a mut usize,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn insert(self, value: V) -> &'a mut V {
+        if matches!(self.slots[self.index], Slot::Tombstone) {
+            *self.tombstones -= 1;
+        }
+        self.slots[self.index] = Slot::Full(self.hash, self.key, value);
+        *self.len += 1;
+        match &mut self.slots[self.index] {
+            Slot::Full(_, _, v) => v,
+            _ => unreachable!("just inserted"),
+        }
+    }
+}
+
+pub struct Iter<'a, K, V, S> {
+    map: &'a HashMap<K, V, S>,
     at: usize,
+    remaining: usize,
 }
 
-impl<'a, K, V> Iterator for Iter<'a, K, V> {
+impl<'a, K, V, S> Iterator for Iter<'a, K, V, S> {
     type Item = (&'a K, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            match self.map.buckets.get(self.bucket) {
-                Some(bucket) => match bucket.get(self.at) {
-                    Some(&(ref k, ref v)) => {
-                        self.at += 1;
-                        break Some((k, v));
-                    }
-                    None => {
-                        self.bucket += 1;
-                        self.at = 0;
-                        continue;
-                    }
-                },
+            match self.map.slots.get(self.at) {
+                Some(Slot::Full(_, k, v)) => {
+                    self.at += 1;
+                    self.remaining -= 1;
+                    break Some((k, v));
+                }
+                Some(_) => {
+                    self.at += 1;
+                    continue;
+                }
                 None => break None,
             }
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
 }
 
-impl<'a, K, V> IntoIterator for &'a HashMap<K, V> {
+impl<'a, K, V, S> ExactSizeIterator for Iter<'a, K, V, S> {
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a HashMap<K, V, S> {
     type Item = (&'a K, &'a V);
 
-    type IntoIter = Iter<'a, K, V>;
+    type IntoIter = Iter<'a, K, V, S>;
 
     fn into_iter(self) -> Self::IntoIter {
         Iter {
+            remaining: self.len,
             map: self,
-            bucket: 0,
             at: 0,
         }
     }
 }
 
+pub struct IterMut<'a, K, V, S> {
+    map: &'a mut HashMap<K, V, S>,
+    at: usize,
+    remaining: usize,
+}
+
+impl<'a, K, V, S> Iterator for IterMut<'a, K, V, S> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let slot = self.map.slots.get_mut(self.at)?;
+            self.at += 1;
+            match slot {
+                Slot::Full(_, k, v) => {
+                    self.remaining -= 1;
+                    let k: *const K = k;
+                    let v: *mut V = v;
+                    // SAFETY: `at` only ever advances, so each slot is handed
+                    // out through this iterator at most once; the `'a`
+                    // references below can't alias one another or the slot
+                    // they came from.
+                    break Some(unsafe { (&*k, &mut *v) });
+                }
+                _ => continue,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V, S> ExactSizeIterator for IterMut<'a, K, V, S> {
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut HashMap<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    type Item = (&'a K, &'a mut V);
+
+    type IntoIter = IterMut<'a, K, V, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+pub struct IntoIter<K, V> {
+    inner: std::vec::IntoIter<Slot<K, V>>,
+    remaining: usize,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for slot in self.inner.by_ref() {
+            if let Slot::Full(_, k, v) = slot {
+                self.remaining -= 1;
+                return Some((k, v));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<K, V, S> IntoIterator for HashMap<K, V, S> {
+    type Item = (K, V);
+
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            remaining: self.len,
+            inner: self.slots.into_iter(),
+        }
+    }
+}
+
+pub struct Drain<'a, K, V, S> {
+    map: &'a mut HashMap<K, V, S>,
+    at: usize,
+    remaining: usize,
+}
+
+impl<'a, K, V, S> Iterator for Drain<'a, K, V, S> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.at < self.map.slots.len() {
+            let slot = mem::replace(&mut self.map.slots[self.at], Slot::Empty);
+            self.at += 1;
+            if let Slot::Full(_, k, v) = slot {
+                self.remaining -= 1;
+                self.map.len -= 1;
+                return Some((k, v));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V, S> ExactSizeIterator for Drain<'a, K, V, S> {
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<'a, K, V, S> Drop for Drain<'a, K, V, S> {
+    fn drop(&mut self) {
+        for _ in self.by_ref() {}
+    }
+}
+
+/// The error returned by [`HashMap::try_reserve`] when the allocator fails
+/// to grow the underlying storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TryReserveError(std::collections::TryReserveError);
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Error for TryReserveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
+    use std::hash::Hasher;
+
+    /// A `BuildHasher` whose output is just the key's bytes, so tests can
+    /// pin down exactly which slot a key lands in.
+    #[derive(Default, Clone)]
+    struct IdentityBuildHasher;
+
+    #[derive(Default)]
+    struct IdentityHasher(u64);
+
+    impl Hasher for IdentityHasher {
+        fn write(&mut self, bytes: &[u8]) {
+            // Keys in these tests are small non-negative integers, whose
+            // native-endian encoding puts the value itself in the first
+            // byte, so this reduces to the identity function on them.
+            if let Some(&first) = bytes.first() {
+                self.0 = u64::from(first);
+            }
+        }
+
+        fn finish(&self) -> u64 {
+            self.0
+        }
+    }
+
+    impl BuildHasher for IdentityBuildHasher {
+        type Hasher = IdentityHasher;
+
+        fn build_hasher(&self) -> IdentityHasher {
+            IdentityHasher::default()
+        }
+    }
 
     #[test]
     fn insert() {
@@ -221,4 +753,201 @@ mod tests {
 
         assert_eq!(map.len(), 4);
     }
+
+    #[test]
+    fn entry_or_insert() {
+        let mut map = HashMap::new();
+        *map.entry("a").or_insert(0) += 1;
+        *map.entry("a").or_insert(0) += 1;
+        assert_eq!(map.get(&"a"), Some(&2));
+    }
+
+    #[test]
+    fn entry_or_insert_with() {
+        let mut map = HashMap::new();
+        map.entry("a").or_insert_with(|| 41);
+        assert_eq!(map.get(&"a"), Some(&41));
+    }
+
+    #[test]
+    fn entry_or_default() {
+        let mut map: HashMap<&str, i32> = HashMap::new();
+        assert_eq!(*map.entry("a").or_default(), 0);
+    }
+
+    #[test]
+    fn entry_and_modify() {
+        let mut map = HashMap::new();
+        map.insert("a", 1);
+        map.entry("a").and_modify(|v| *v += 1).or_insert(100);
+        map.entry("b").and_modify(|v| *v += 1).or_insert(100);
+        assert_eq!(map.get(&"a"), Some(&2));
+        assert_eq!(map.get(&"b"), Some(&100));
+    }
+
+    #[test]
+    fn entry_occupied_remove() {
+        let mut map = HashMap::new();
+        map.insert("a", 1);
+        match map.entry("a") {
+            Entry::Occupied(e) => assert_eq!(e.remove(), 1),
+            Entry::Vacant(_) => unreachable!(),
+        }
+        assert_eq!(map.len(), 0);
+        assert!(!map.contains_key(&"a"));
+    }
+
+    #[test]
+    fn survives_many_inserts_and_removals() {
+        let mut map = HashMap::new();
+        for i in 0..500 {
+            map.insert(i, i * 2);
+        }
+        for i in 0..250 {
+            assert_eq!(map.remove(&i), Some((i, i * 2)));
+        }
+        for i in 0..250 {
+            assert_eq!(map.get(&i), None);
+        }
+        for i in 250..500 {
+            assert_eq!(map.get(&i), Some(&(i * 2)));
+        }
+        assert_eq!(map.len(), 250);
+
+        for i in 0..250 {
+            map.insert(i, i * 3);
+        }
+        assert_eq!(map.len(), 500);
+        for i in 0..250 {
+            assert_eq!(map.get(&i), Some(&(i * 3)));
+        }
+    }
+
+    #[test]
+    fn reserve_grows_up_front() {
+        let mut map = HashMap::new();
+        map.reserve(100);
+        let capacity_after_reserve = map.slots.len();
+        assert!(capacity_after_reserve * 7 >= 100 * 8);
+
+        for i in 0..100 {
+            map.insert(i, i);
+        }
+        assert_eq!(map.slots.len(), capacity_after_reserve);
+    }
+
+    #[test]
+    fn try_reserve_ok() {
+        let mut map: HashMap<&str, i32> = HashMap::new();
+        assert!(map.try_reserve(16).is_ok());
+        assert!(map.slots.len() * 7 >= 16 * 8);
+    }
+
+    #[test]
+    fn try_reserve_huge_additional_errs_instead_of_overflowing() {
+        // `additional` close to `usize::MAX` must not let `required * 8`
+        // wrap around to a small number and report success while barely
+        // growing the table; it should reliably fail to allocate instead.
+        let mut map: HashMap<&str, i32> = HashMap::new();
+        assert!(map.try_reserve(usize::MAX - 1).is_err());
+    }
+
+    #[test]
+    fn try_insert_reports_result() {
+        let mut map = HashMap::new();
+        assert_eq!(map.try_insert("a", 1), Ok(None));
+        assert_eq!(map.try_insert("a", 2), Ok(Some(1)));
+        assert_eq!(map.get(&"a"), Some(&2));
+    }
+
+    #[test]
+    fn try_reserve_accounts_for_tombstones() {
+        // With a 4-slot table, 3 inserts plus a remove leave one tombstone
+        // and zero empty slots (len 2, tombstones 1). The growth check must
+        // see that the table is full even though `len` alone says otherwise,
+        // or the very next probe for a missing key never finds an `Empty`
+        // slot to stop at.
+        let mut map: HashMap<i32, i32, IdentityBuildHasher> =
+            HashMap::with_capacity_and_hasher(0, IdentityBuildHasher);
+        map.insert(0, 0);
+        map.insert(1, 1);
+        map.insert(2, 2);
+        map.remove(&0);
+        map.try_insert(3, 3).unwrap();
+
+        assert_eq!(map.get(&999), None);
+    }
+
+    #[test]
+    fn get_mut() {
+        let mut map = HashMap::new();
+        map.insert("a", 1);
+        *map.get_mut(&"a").unwrap() += 1;
+        assert_eq!(map.get(&"a"), Some(&2));
+        assert_eq!(map.get_mut(&"b"), None);
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut map = HashMap::new();
+        map.insert("a", 1);
+        map.insert("b", 2);
+
+        for (_, v) in map.iter_mut() {
+            *v *= 10;
+        }
+
+        assert_eq!(map.get(&"a"), Some(&10));
+        assert_eq!(map.get(&"b"), Some(&20));
+        assert_eq!(map.iter_mut().len(), 2);
+    }
+
+    #[test]
+    fn into_iterator_for_mut_ref() {
+        let mut map = HashMap::new();
+        map.insert("a", 1);
+        map.insert("b", 2);
+
+        for (_, v) in &mut map {
+            *v *= 10;
+        }
+
+        assert_eq!(map.get(&"a"), Some(&10));
+        assert_eq!(map.get(&"b"), Some(&20));
+    }
+
+    #[test]
+    fn into_iter() {
+        let mut map = HashMap::new();
+        map.insert("a", 1);
+        map.insert("b", 2);
+
+        let mut pairs: Vec<_> = map.into_iter().collect();
+        pairs.sort_unstable();
+        assert_eq!(pairs, vec![("a", 1), ("b", 2)]);
+    }
+
+    #[test]
+    fn drain() {
+        let mut map = HashMap::new();
+        map.insert("a", 1);
+        map.insert("b", 2);
+
+        let mut drained: Vec<_> = map.drain().collect();
+        drained.sort_unstable();
+        assert_eq!(drained, vec![("a", 1), ("b", 2)]);
+        assert!(map.is_empty());
+        assert_eq!(map.get(&"a"), None);
+    }
+
+    #[test]
+    fn drain_dropped_early_still_empties_the_map() {
+        let mut map = HashMap::new();
+        map.insert("a", 1);
+        map.insert("b", 2);
+
+        drop(map.drain());
+
+        assert!(map.is_empty());
+    }
 }
@@ -0,0 +1,261 @@
+use std::{
+    borrow::Borrow,
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hash},
+    iter::Chain,
+};
+
+use crate::HashMap;
+
+pub struct HashSet<T, S = RandomState> {
+    map: HashMap<T, (), S>,
+}
+
+impl<T> HashSet<T, RandomState> {
+    pub fn new() -> Self {
+        Self { map: HashMap::new() }
+    }
+}
+
+impl<T> Default for HashSet<T, RandomState> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S> HashSet<T, S> {
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            map: HashMap::with_hasher(hash_builder),
+        }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        Self {
+            map: HashMap::with_capacity_and_hasher(capacity, hash_builder),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl<T, S> HashSet<T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    pub fn insert(&mut self, value: T) -> bool {
+        self.map.insert(value, ()).is_none()
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(value)
+    }
+
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.remove(value).is_some()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T, S> {
+        Iter {
+            inner: (&self.map).into_iter(),
+        }
+    }
+
+    pub fn difference<'a>(&'a self, other: &'a HashSet<T, S>) -> Difference<'a, T, S> {
+        Difference {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    pub fn intersection<'a>(&'a self, other: &'a HashSet<T, S>) -> Intersection<'a, T, S> {
+        Intersection {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a HashSet<T, S>,
+    ) -> SymmetricDifference<'a, T, S> {
+        SymmetricDifference {
+            iter: self.difference(other).chain(other.difference(self)),
+        }
+    }
+
+    pub fn union<'a>(&'a self, other: &'a HashSet<T, S>) -> Union<'a, T, S> {
+        Union {
+            iter: self.iter().chain(other.difference(self)),
+        }
+    }
+}
+
+pub struct Iter<'a, T, S> {
+    inner: crate::Iter<'a, T, (), S>,
+}
+
+impl<'a, T, S> Iterator for Iter<'a, T, S> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(value, _)| value)
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a HashSet<T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct Difference<'a, T, S> {
+    iter: Iter<'a, T, S>,
+    other: &'a HashSet<T, S>,
+}
+
+impl<'a, T, S> Iterator for Difference<'a, T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let value = self.iter.next()?;
+            if !self.other.contains(value) {
+                return Some(value);
+            }
+        }
+    }
+}
+
+pub struct Intersection<'a, T, S> {
+    iter: Iter<'a, T, S>,
+    other: &'a HashSet<T, S>,
+}
+
+impl<'a, T, S> Iterator for Intersection<'a, T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let value = self.iter.next()?;
+            if self.other.contains(value) {
+                return Some(value);
+            }
+        }
+    }
+}
+
+pub struct SymmetricDifference<'a, T, S> {
+    iter: Chain<Difference<'a, T, S>, Difference<'a, T, S>>,
+}
+
+impl<'a, T, S> Iterator for SymmetricDifference<'a, T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+pub struct Union<'a, T, S> {
+    iter: Chain<Iter<'a, T, S>, Difference<'a, T, S>>,
+}
+
+impl<'a, T, S> Iterator for Union<'a, T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_contains() {
+        let mut set = HashSet::new();
+        assert!(set.insert("a"));
+        assert!(!set.insert("a"));
+        assert!(set.contains("a"));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn remove() {
+        let mut set = HashSet::new();
+        set.insert("a");
+        assert!(set.remove("a"));
+        assert!(!set.contains("a"));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn set_algebra() {
+        let mut a = HashSet::new();
+        a.insert(1);
+        a.insert(2);
+        a.insert(3);
+
+        let mut b = HashSet::new();
+        b.insert(2);
+        b.insert(3);
+        b.insert(4);
+
+        let mut union: Vec<_> = a.union(&b).copied().collect();
+        union.sort_unstable();
+        assert_eq!(union, vec![1, 2, 3, 4]);
+
+        let mut intersection: Vec<_> = a.intersection(&b).copied().collect();
+        intersection.sort_unstable();
+        assert_eq!(intersection, vec![2, 3]);
+
+        let mut difference: Vec<_> = a.difference(&b).copied().collect();
+        difference.sort_unstable();
+        assert_eq!(difference, vec![1]);
+
+        let mut symmetric_difference: Vec<_> = a.symmetric_difference(&b).copied().collect();
+        symmetric_difference.sort_unstable();
+        assert_eq!(symmetric_difference, vec![1, 4]);
+    }
+}